@@ -1,15 +1,20 @@
 #[derive(Debug, Clone)]
 pub enum GuiEvent {
     UpdateStatus(String, String),
-    UpdateText(String, String),
+    // text, append/replace decision, optional origin timestamp (epoch ns)
+    UpdateText(String, String, Option<u64>),
     SetRecording(bool),
     ShowError(String),
     SetWindowProperties { transparent: bool, always_on_top: bool },
     TriggerCallback(String, String),
+    AudioLevel(f32),
 }
 
 impl GuiEvent {
     pub fn is_high_frequency(&self) -> bool {
-        matches!(self, GuiEvent::UpdateText(_, _) | GuiEvent::UpdateStatus(_, _))
+        matches!(
+            self,
+            GuiEvent::UpdateText(_, _, _) | GuiEvent::UpdateStatus(_, _) | GuiEvent::AudioLevel(_)
+        )
     }
 }
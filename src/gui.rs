@@ -1,14 +1,30 @@
 use eframe::egui;
+use egui_dock::{DockArea, DockState};
 use crossbeam_channel::Receiver;
 use std::sync::{Arc, Mutex};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use pyo3::prelude::*;
 use crate::events::GuiEvent;
+use crate::inspector::EventLog;
+use crate::PromiseQueue;
+
+const LATENCY_WINDOW: usize = 200;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Transcript,
+    Inspector,
+}
 
 pub struct GuiApp {
     receiver: Receiver<GuiEvent>,
     callbacks: Arc<Mutex<HashMap<String, Py<PyAny>>>>,
-    
+    promise_queue: PromiseQueue,
+    clock_offset_ns: Arc<Mutex<i64>>,
+    dock_state: Option<DockState<Tab>>,
+    event_log: EventLog,
+    latency_samples_ms: VecDeque<f64>,
+
     status_text: String,
     status_color: egui::Color32,
     recognized_text: String,
@@ -19,7 +35,8 @@ pub struct GuiApp {
     
     thought_linking_enabled: bool,
     vad_threshold: f32,
-    
+    audio_level_ema: f32,
+
     last_update: std::time::Instant,
     update_batch: Vec<GuiEvent>,
 }
@@ -28,11 +45,18 @@ impl GuiApp {
     pub fn new(
         _cc: &eframe::CreationContext<'_>,
         receiver: Receiver<GuiEvent>,
-        callbacks: Arc<Mutex<HashMap<String, Py<PyAny>>>>
+        callbacks: Arc<Mutex<HashMap<String, Py<PyAny>>>>,
+        promise_queue: PromiseQueue,
+        clock_offset_ns: Arc<Mutex<i64>>,
     ) -> Self {
         Self {
             receiver,
             callbacks,
+            promise_queue,
+            clock_offset_ns,
+            dock_state: Some(DockState::new(vec![Tab::Transcript, Tab::Inspector])),
+            event_log: EventLog::new(),
+            latency_samples_ms: VecDeque::with_capacity(LATENCY_WINDOW),
             status_text: "Ready".to_string(),
             status_color: egui::Color32::WHITE,
             recognized_text: String::new(),
@@ -42,6 +66,7 @@ impl GuiApp {
             settings_open: false,
             thought_linking_enabled: false,
             vad_threshold: 2.0,
+            audio_level_ema: 0.0,
             last_update: std::time::Instant::now(),
             update_batch: Vec::new(),
         }
@@ -57,12 +82,13 @@ impl GuiApp {
         }
         
         for event in &self.update_batch {
+            self.event_log.record(event);
             match event {
                 GuiEvent::UpdateStatus(status, color) => {
                     self.status_text = status.clone();
                     self.status_color = parse_color(color);
                 }
-                GuiEvent::UpdateText(text, decision) => {
+                GuiEvent::UpdateText(text, decision, origin_timestamp_ns) => {
                     match decision.as_str() {
                         "APPEND_WITH_SPACE" => {
                             if !self.recognized_text.is_empty() {
@@ -78,6 +104,9 @@ impl GuiApp {
                         }
                     }
                     self.text_color = egui::Color32::WHITE;
+                    if let Some(origin_ns) = origin_timestamp_ns {
+                        self.record_latency(*origin_ns);
+                    }
                 }
                 GuiEvent::SetRecording(recording) => {
                     self.is_recording = *recording;
@@ -98,6 +127,9 @@ impl GuiApp {
                 GuiEvent::TriggerCallback(callback_name, data) => {
                     self.trigger_python_callback(callback_name, data);
                 }
+                GuiEvent::AudioLevel(level) => {
+                    self.audio_level_ema = 0.2 * level + 0.8 * self.audio_level_ema;
+                }
             }
         }
     }
@@ -113,6 +145,176 @@ impl GuiApp {
                 });
             }
         }
+        self.fulfill_next_promise(callback_name, data);
+    }
+
+    // Drains the queue before taking the GIL; next_event() always takes the
+    // GIL first and the queue second, so doing it the other way round here
+    // can deadlock against it.
+    fn fulfill_next_promise(&self, callback_name: &str, data: &str) {
+        let pending: Vec<_> = self.promise_queue.lock().unwrap().drain(..).collect();
+
+        let mut leftover = VecDeque::with_capacity(pending.len());
+        let mut delivered = false;
+        Python::with_gil(|py| {
+            for (future, event_loop) in pending {
+                if delivered {
+                    leftover.push_back((future, event_loop));
+                    continue;
+                }
+                let bound = future.bind(py);
+                match bound.call_method0("done").and_then(|d| d.is_truthy()) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        let sent = (|| -> PyResult<()> {
+                            let set_result = bound.getattr("set_result")?;
+                            let value = (callback_name.to_string(), data.to_string());
+                            event_loop.bind(py).call_method1("call_soon_threadsafe", (set_result, value))?;
+                            Ok(())
+                        })();
+                        match sent {
+                            Ok(()) => delivered = true,
+                            Err(_) => leftover.push_back((future, event_loop)),
+                        }
+                    }
+                    Err(_) => leftover.push_back((future, event_loop)),
+                }
+            }
+        });
+
+        if !leftover.is_empty() {
+            let mut queue = self.promise_queue.lock().unwrap();
+            for entry in leftover.into_iter().rev() {
+                queue.push_front(entry);
+            }
+        }
+    }
+
+    fn record_latency(&mut self, origin_timestamp_ns: u64) {
+        let now_ns = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as i64;
+        let offset_ns = *self.clock_offset_ns.lock().unwrap();
+        let latency_ns = now_ns - origin_timestamp_ns as i64 + offset_ns;
+
+        if self.latency_samples_ms.len() >= LATENCY_WINDOW {
+            self.latency_samples_ms.pop_front();
+        }
+        self.latency_samples_ms.push_back(latency_ns as f64 / 1_000_000.0);
+    }
+
+    fn latency_percentile(&self, p: f64) -> Option<f64> {
+        if self.latency_samples_ms.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<f64> = self.latency_samples_ms.iter().copied().collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let index = ((sorted.len() as f64 - 1.0) * p).round() as usize;
+        sorted.get(index).copied()
+    }
+
+    fn emit_settings_changed(&mut self, key: &str, value: serde_json::Value) {
+        let payload = serde_json::json!({ "key": key, "value": value }).to_string();
+        let event = GuiEvent::TriggerCallback("settings_changed".to_string(), payload.clone());
+        self.event_log.record(&event);
+        self.trigger_python_callback("settings_changed", &payload);
+    }
+
+    fn draw_transcript(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.colored_label(self.status_color, &self.status_text);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("⚙").clicked() {
+                    self.settings_open = true;
+                }
+                if ui.button("🐛").clicked() {
+                    self.debug_mode = !self.debug_mode;
+                }
+            });
+        });
+
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                ui.colored_label(self.text_color, &self.recognized_text);
+            });
+
+        if self.debug_mode {
+            ui.separator();
+            ui.collapsing("Debug Info", |ui| {
+                ui.label(format!("Recording: {}", self.is_recording));
+                ui.label(format!("Text length: {}", self.recognized_text.len()));
+                ui.label(format!("Last update: {:?}", self.last_update.elapsed()));
+
+                let p50 = self.latency_percentile(0.50);
+                let p95 = self.latency_percentile(0.95);
+                match (p50, p95) {
+                    (Some(p50), Some(p95)) => {
+                        ui.label(format!("Latency p50/p95: {:.1}ms / {:.1}ms", p50, p95));
+                    }
+                    _ => {
+                        ui.label("Latency p50/p95: n/a (no timestamped frames yet)");
+                    }
+                }
+            });
+        }
+    }
+
+    fn draw_inspector(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            ui.text_edit_singleline(&mut self.event_log.variant_filter);
+            ui.checkbox(&mut self.event_log.paused, "Pause capture");
+        });
+
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                egui::Grid::new("event_inspector_grid")
+                    .striped(true)
+                    .num_columns(3)
+                    .show(ui, |ui| {
+                        ui.strong("t+ms");
+                        ui.strong("event");
+                        ui.strong("payload");
+                        ui.end_row();
+
+                        let capture_start = self.event_log.capture_start();
+                        for entry in self.event_log.filtered() {
+                            ui.label(format!("{}", entry.timestamp.duration_since(capture_start).as_millis()));
+                            ui.label(entry.variant);
+                            ui.label(&entry.payload);
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
+
+struct GuiAppTabViewer<'a> {
+    app: &'a mut GuiApp,
+}
+
+impl<'a> egui_dock::TabViewer for GuiAppTabViewer<'a> {
+    type Tab = Tab;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
+        match tab {
+            Tab::Transcript => "Transcript".into(),
+            Tab::Inspector => "Event Inspector".into(),
+        }
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
+        match tab {
+            Tab::Transcript => self.app.draw_transcript(ui),
+            Tab::Inspector => self.app.draw_inspector(ui),
+        }
     }
 }
 
@@ -123,47 +325,44 @@ impl eframe::App for GuiApp {
     
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.process_events();
-        
-        egui::CentralPanel::default()
-            .frame(egui::Frame::none())
-            .show(ctx, |ui| {
-                ui.horizontal(|ui| {
-                    ui.colored_label(self.status_color, &self.status_text);
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.button("⚙").clicked() {
-                            self.settings_open = true;
-                        }
-                        if ui.button("🐛").clicked() {
-                            self.debug_mode = !self.debug_mode;
-                        }
-                    });
-                });
-                
-                ui.separator();
-                
-                egui::ScrollArea::vertical()
-                    .auto_shrink([false; 2])
-                    .show(ui, |ui| {
-                        ui.colored_label(self.text_color, &self.recognized_text);
-                    });
-                
-                if self.debug_mode {
-                    ui.separator();
-                    ui.collapsing("Debug Info", |ui| {
-                        ui.label(format!("Recording: {}", self.is_recording));
-                        ui.label(format!("Text length: {}", self.recognized_text.len()));
-                        ui.label(format!("Last update: {:?}", self.last_update.elapsed()));
-                    });
-                }
-            });
-        
+
+        let mut dock_state = self.dock_state.take().expect("dock_state taken twice");
+        DockArea::new(&mut dock_state)
+            .style(egui_dock::Style::from_egui(ctx.style().as_ref()))
+            .show(ctx, &mut GuiAppTabViewer { app: self });
+        self.dock_state = Some(dock_state);
+
         if self.settings_open {
             egui::Window::new("Settings")
                 .open(&mut self.settings_open)
                 .show(ctx, |ui| {
-                    ui.checkbox(&mut self.thought_linking_enabled, "Enable Thought Linking");
-                    ui.add(egui::Slider::new(&mut self.vad_threshold, 0.5..=5.0)
-                        .text("VAD Pause Threshold"));
+                    let thought_linking_response =
+                        ui.checkbox(&mut self.thought_linking_enabled, "Enable Thought Linking");
+                    if thought_linking_response.changed() {
+                        self.emit_settings_changed(
+                            "thought_linking_enabled",
+                            serde_json::json!(self.thought_linking_enabled),
+                        );
+                    }
+
+                    let vad_threshold_response = ui.add(
+                        egui::Slider::new(&mut self.vad_threshold, 0.5..=5.0)
+                            .text("VAD Pause Threshold"),
+                    );
+                    if vad_threshold_response.drag_stopped() || vad_threshold_response.lost_focus() {
+                        self.emit_settings_changed("vad_threshold", serde_json::json!(self.vad_threshold));
+                    }
+
+                    ui.separator();
+                    ui.label("Microphone Level");
+                    let speaking = self.audio_level_ema > self.vad_threshold;
+                    let bar_color = if speaking { egui::Color32::RED } else { egui::Color32::GREEN };
+                    let fraction = (self.audio_level_ema / (self.vad_threshold * 2.0)).clamp(0.0, 1.0);
+                    ui.add(
+                        egui::ProgressBar::new(fraction)
+                            .fill(bar_color)
+                            .text(format!("{:.2}", self.audio_level_ema)),
+                    );
                 });
         }
         
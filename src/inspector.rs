@@ -0,0 +1,84 @@
+use crate::events::GuiEvent;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+const CAPACITY: usize = 1000;
+// Separate, smaller ring for is_high_frequency() events so a fast stream
+// like AudioLevel can't evict the low-frequency events the inspector exists
+// to surface.
+const HIGH_FREQUENCY_CAPACITY: usize = 200;
+
+#[derive(Clone)]
+pub struct EventLogEntry {
+    pub timestamp: Instant,
+    pub variant: &'static str,
+    pub payload: String,
+}
+
+pub struct EventLog {
+    entries: VecDeque<EventLogEntry>,
+    high_frequency_entries: VecDeque<EventLogEntry>,
+    capture_start: Instant,
+    pub paused: bool,
+    pub variant_filter: String,
+}
+
+impl EventLog {
+    pub fn new() -> Self {
+        Self {
+            entries: VecDeque::with_capacity(CAPACITY),
+            high_frequency_entries: VecDeque::with_capacity(HIGH_FREQUENCY_CAPACITY),
+            capture_start: Instant::now(),
+            paused: false,
+            variant_filter: String::new(),
+        }
+    }
+
+    pub fn record(&mut self, event: &GuiEvent) {
+        if self.paused {
+            return;
+        }
+
+        let (buffer, capacity) = if event.is_high_frequency() {
+            (&mut self.high_frequency_entries, HIGH_FREQUENCY_CAPACITY)
+        } else {
+            (&mut self.entries, CAPACITY)
+        };
+        if buffer.len() >= capacity {
+            buffer.pop_front();
+        }
+        buffer.push_back(EventLogEntry {
+            timestamp: Instant::now(),
+            variant: variant_name(event),
+            payload: format!("{:?}", event),
+        });
+    }
+
+    pub fn capture_start(&self) -> Instant {
+        self.capture_start
+    }
+
+    pub fn filtered(&self) -> impl Iterator<Item = &EventLogEntry> {
+        let filter = self.variant_filter.to_lowercase();
+        let mut merged: Vec<&EventLogEntry> = self
+            .entries
+            .iter()
+            .chain(self.high_frequency_entries.iter())
+            .filter(move |entry| filter.is_empty() || entry.variant.to_lowercase().contains(&filter))
+            .collect();
+        merged.sort_by_key(|entry| entry.timestamp);
+        merged.into_iter()
+    }
+}
+
+fn variant_name(event: &GuiEvent) -> &'static str {
+    match event {
+        GuiEvent::UpdateStatus(_, _) => "UpdateStatus",
+        GuiEvent::UpdateText(_, _, _) => "UpdateText",
+        GuiEvent::SetRecording(_) => "SetRecording",
+        GuiEvent::ShowError(_) => "ShowError",
+        GuiEvent::SetWindowProperties { .. } => "SetWindowProperties",
+        GuiEvent::TriggerCallback(_, _) => "TriggerCallback",
+        GuiEvent::AudioLevel(_) => "AudioLevel",
+    }
+}
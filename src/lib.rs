@@ -1,19 +1,26 @@
 use pyo3::prelude::*;
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use std::sync::{Arc, Mutex};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 mod gui;
 mod events;
+mod inspector;
+mod promise;
+mod ws_client;
 
 use gui::GuiApp;
 use events::GuiEvent;
+use promise::{PromisePruner, RustPromise};
 
 #[pyclass(frozen)]
 pub struct GuiController {
     event_sender: Sender<GuiEvent>,
     event_receiver: Arc<Mutex<Option<Receiver<GuiEvent>>>>,
     callback_registry: Arc<Mutex<HashMap<String, Py<PyAny>>>>,
+    // (future, event_loop) pairs awaiting the next TriggerCallback, FIFO.
+    promise_queue: PromiseQueue,
+    clock_offset_ns: Arc<Mutex<i64>>,
 }
 
 #[pymethods]
@@ -23,28 +30,49 @@ impl GuiController {
         let (tx, rx) = unbounded();
         let callbacks = Arc::new(Mutex::new(HashMap::new()));
         let receiver = Arc::new(Mutex::new(Some(rx)));
-        
+
         Self {
             event_sender: tx,
             event_receiver: receiver,
             callback_registry: callbacks,
+            promise_queue: Arc::new(Mutex::new(VecDeque::new())),
+            clock_offset_ns: Arc::new(Mutex::new(0)),
         }
     }
-    
+
     pub fn run(&self) -> PyResult<()> {
         let receiver = self.event_receiver.lock().unwrap().take()
             .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("GUI already running"))?;
-        
+
         let callbacks_clone = self.callback_registry.clone();
-        
-        run_gui_thread(receiver, callbacks_clone);
+        let promise_queue_clone = self.promise_queue.clone();
+        let clock_offset_clone = self.clock_offset_ns.clone();
+
+        run_gui_thread(receiver, callbacks_clone, promise_queue_clone, clock_offset_clone);
         Ok(())
     }
-    
+
     pub fn register_callback(&self, name: String, callback: Py<PyAny>) -> PyResult<()> {
         self.callback_registry.lock().unwrap().insert(name, callback);
         Ok(())
     }
+
+    // Resolves to (name, data) on the next TriggerCallback, oldest waiter first.
+    pub fn next_event(&self, py: Python<'_>) -> PyResult<RustPromise> {
+        let asyncio = py.import("asyncio")?;
+        let event_loop = asyncio.call_method0("get_event_loop")?;
+        let future = event_loop.call_method0("create_future")?;
+
+        self.promise_queue
+            .lock()
+            .unwrap()
+            .push_back((future.clone().unbind(), event_loop.clone().unbind()));
+
+        let pruner = Py::new(py, PromisePruner::new(self.promise_queue.clone(), future.clone().unbind()))?;
+        future.call_method1("add_done_callback", (pruner,))?;
+
+        Ok(RustPromise::new(future.unbind()))
+    }
     
     pub fn update_status(&self, status: String, color: String) -> PyResult<()> {
         let event = GuiEvent::UpdateStatus(status, color);
@@ -53,11 +81,17 @@ impl GuiController {
     }
     
     pub fn update_text(&self, text: String, decision: String) -> PyResult<()> {
-        let event = GuiEvent::UpdateText(text, decision);
+        let event = GuiEvent::UpdateText(text, decision, None);
         self.event_sender.send(event)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
     }
-    
+
+    pub fn update_text_with_origin(&self, text: String, decision: String, origin_timestamp_ns: u64) -> PyResult<()> {
+        let event = GuiEvent::UpdateText(text, decision, Some(origin_timestamp_ns));
+        self.event_sender.send(event)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
     pub fn set_recording(&self, is_recording: bool) -> PyResult<()> {
         let event = GuiEvent::SetRecording(is_recording);
         self.event_sender.send(event)
@@ -75,12 +109,65 @@ impl GuiController {
         self.event_sender.send(event)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
     }
+
+    pub fn push_audio_level(&self, level: f32) -> PyResult<()> {
+        let event = GuiEvent::AudioLevel(level);
+        self.event_sender.send(event)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    // Connects to a workshop-box-ui transcription WebSocket (e.g.
+    // ws://localhost:8765), feeding decoded frames into the same event
+    // pipeline as update_status/update_text.
+    pub fn connect_websocket(&self, url: String) -> PyResult<()> {
+        let sender = self.event_sender.clone();
+        let clock_offset = self.clock_offset_ns.clone();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new()
+                .expect("failed to start websocket client runtime");
+            runtime.block_on(ws_client::run(url, sender, clock_offset));
+        });
+        Ok(())
+    }
+}
+
+type PromiseQueue = Arc<Mutex<VecDeque<(Py<PyAny>, Py<PyAny>)>>>;
+
+// Rejects still-pending next_event() futures on drop, so they don't hang
+// forever if the GUI thread exits or panics.
+struct PromiseQueueGuard(PromiseQueue);
+
+impl Drop for PromiseQueueGuard {
+    fn drop(&mut self) {
+        let pending: Vec<_> = self.0.lock().unwrap().drain(..).collect();
+        Python::with_gil(|py| {
+            for (future, event_loop) in pending {
+                let reject = || -> PyResult<()> {
+                    let future = future.bind(py);
+                    if future.call_method0("done")?.is_truthy()? {
+                        return Ok(());
+                    }
+                    let err = py.import("builtins")?
+                        .getattr("RuntimeError")?
+                        .call1(("GUI thread exited",))?;
+                    let set_exception = future.getattr("set_exception")?;
+                    event_loop.bind(py).call_method1("call_soon_threadsafe", (set_exception, err))?;
+                    Ok(())
+                };
+                let _ = reject();
+            }
+        });
+    }
 }
 
 fn run_gui_thread(
     receiver: Receiver<GuiEvent>,
-    callbacks: Arc<Mutex<HashMap<String, Py<PyAny>>>>
+    callbacks: Arc<Mutex<HashMap<String, Py<PyAny>>>>,
+    promise_queue: PromiseQueue,
+    clock_offset_ns: Arc<Mutex<i64>>,
 ) {
+    let _promise_guard = PromiseQueueGuard(promise_queue.clone());
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_title("PersonalParakeet v3")
@@ -90,16 +177,17 @@ fn run_gui_thread(
             .with_resizable(true),
         ..Default::default()
     };
-    
+
     eframe::run_native(
         "PersonalParakeet v3",
         options,
-        Box::new(move |cc| Ok(Box::new(GuiApp::new(cc, receiver, callbacks)))),
+        Box::new(move |cc| Ok(Box::new(GuiApp::new(cc, receiver, callbacks, promise_queue, clock_offset_ns)))),
     ).unwrap();
 }
 
 #[pymodule]
 fn personalparakeet_ui(m: &pyo3::Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<GuiController>()?;
+    m.add_class::<RustPromise>()?;
     Ok(())
 }
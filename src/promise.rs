@@ -0,0 +1,50 @@
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+
+use crate::PromiseQueue;
+
+// Wraps an asyncio.Future so __await__ delegates to it directly.
+#[pyclass]
+pub struct RustPromise {
+    future: Py<PyAny>,
+}
+
+impl RustPromise {
+    pub fn new(future: Py<PyAny>) -> Self {
+        Self { future }
+    }
+}
+
+#[pymethods]
+impl RustPromise {
+    fn __await__(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        self.future.bind(py).call_method0("__await__").map(|o| o.unbind())
+    }
+}
+
+// add_done_callback target that removes a next_event() queue entry once its
+// future completes, even if that happened via cancellation/timeout rather
+// than fulfill_next_promise.
+#[pyclass]
+pub struct PromisePruner {
+    queue: PromiseQueue,
+    target: Py<PyAny>,
+}
+
+impl PromisePruner {
+    pub fn new(queue: PromiseQueue, target: Py<PyAny>) -> Self {
+        Self { queue, target }
+    }
+}
+
+#[pymethods]
+impl PromisePruner {
+    #[pyo3(signature = (*_args))]
+    fn __call__(&self, py: Python<'_>, _args: &Bound<'_, PyTuple>) -> PyResult<()> {
+        self.queue
+            .lock()
+            .unwrap()
+            .retain(|(future, _)| !future.bind(py).is(self.target.bind(py)));
+        Ok(())
+    }
+}
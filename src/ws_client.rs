@@ -0,0 +1,135 @@
+use crate::events::GuiEvent;
+use crossbeam_channel::Sender;
+use futures_util::{SinkExt, StreamExt};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::timeout;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Reconnects every 3s; clock_offset_ns is refreshed on each (re)connect.
+pub async fn run(url: String, sender: Sender<GuiEvent>, clock_offset_ns: Arc<Mutex<i64>>) {
+    loop {
+        match connect_async(&url).await {
+            Ok((ws_stream, _)) => {
+                let (mut ws_sender, mut ws_receiver) = ws_stream.split();
+
+                if let Err(e) =
+                    sync_clock(&mut ws_sender, &mut ws_receiver, &clock_offset_ns, &sender).await
+                {
+                    eprintln!("Clock sync with {} failed, continuing without it: {}", url, e);
+                }
+
+                while let Some(msg) = ws_receiver.next().await {
+                    match msg {
+                        Ok(Message::Text(text)) => {
+                            if let Some(event) = parse_event(&text) {
+                                if sender.send(event).is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                        Ok(Message::Close(_)) | Err(_) => break,
+                        _ => {}
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to connect to {}: {}", url, e);
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(3)).await;
+    }
+}
+
+type WsSender = futures_util::stream::SplitSink<
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
+    Message,
+>;
+type WsReceiver = futures_util::stream::SplitStream<
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
+>;
+
+// NTP-style offset = ((t1-t0)+(t2-t3))/2, backend_clock - local_clock.
+// A non-pong frame seen while waiting is a live frame, not the handshake
+// reply, so it's forwarded through `sender` rather than dropped.
+async fn sync_clock(
+    ws_sender: &mut WsSender,
+    ws_receiver: &mut WsReceiver,
+    clock_offset_ns: &Arc<Mutex<i64>>,
+    sender: &Sender<GuiEvent>,
+) -> Result<(), String> {
+    let t0 = now_ns();
+    let ping = serde_json::json!({ "type": "ping", "t0": t0 }).to_string();
+    ws_sender
+        .send(Message::Text(ping))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let deadline = tokio::time::Instant::now() + HANDSHAKE_TIMEOUT;
+    loop {
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            return Err("timed out waiting for clock-sync pong".to_string());
+        }
+
+        let msg = match timeout(remaining, ws_receiver.next()).await {
+            Ok(Some(Ok(msg))) => msg,
+            Ok(Some(Err(e))) => return Err(e.to_string()),
+            Ok(None) => return Err("connection closed before pong".to_string()),
+            Err(_) => return Err("timed out waiting for clock-sync pong".to_string()),
+        };
+
+        let Message::Text(text) = msg else {
+            continue;
+        };
+        let t3 = now_ns() as i64;
+
+        let data: serde_json::Value = match serde_json::from_str(&text) {
+            Ok(data) => data,
+            Err(_) => continue,
+        };
+
+        if data["type"] != "pong" {
+            if let Some(event) = parse_event(&text) {
+                let _ = sender.send(event);
+            }
+            continue;
+        }
+
+        let t1 = data["t1"].as_u64().ok_or("pong missing t1")? as i64;
+        let t2 = data["t2"].as_u64().ok_or("pong missing t2")? as i64;
+        *clock_offset_ns.lock().unwrap() = ((t1 - t0 as i64) + (t2 - t3)) / 2;
+        return Ok(());
+    }
+}
+
+fn now_ns() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64
+}
+
+fn parse_event(text: &str) -> Option<GuiEvent> {
+    let data: serde_json::Value = serde_json::from_str(text).ok()?;
+    match data["type"].as_str()? {
+        "transcription" => {
+            let text = data["text"].as_str().unwrap_or("").to_string();
+            let decision = match data["mode"].as_str().unwrap_or("") {
+                "append" => "APPEND_WITH_SPACE",
+                _ => "REPLACE",
+            };
+            let origin_timestamp_ns = data["origin_timestamp_ns"].as_u64();
+            Some(GuiEvent::UpdateText(text, decision.to_string(), origin_timestamp_ns))
+        }
+        "status" => {
+            let status = data["status"].as_str().unwrap_or("").to_string();
+            let color = data["color"].as_str().unwrap_or("white").to_string();
+            Some(GuiEvent::UpdateStatus(status, color))
+        }
+        _ => None,
+    }
+}
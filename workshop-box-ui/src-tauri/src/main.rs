@@ -10,6 +10,8 @@ struct TranscriptionPayload {
     text: String,
     mode: String,
     confidence: f32,
+    // Backend epoch nanoseconds the transcription was produced at, if sent
+    origin_timestamp_ns: Option<u64>,
 }
 
 // Learn more about Tauri commands at https://tauri.app/v1/guides/features/command
@@ -51,6 +53,7 @@ async fn connect_to_python_backend(app_handle: tauri::AppHandle) {
                                         text: data["text"].as_str().unwrap_or("").to_string(),
                                         mode: data["mode"].as_str().unwrap_or("standard").to_string(),
                                         confidence: data["confidence"].as_f64().unwrap_or(0.9) as f32,
+                                        origin_timestamp_ns: data["origin_timestamp_ns"].as_u64(),
                                     });
                                 }
                             }